@@ -1,39 +1,222 @@
-use std::env; // Brings `env::args` into scope so we can read command-line arguments.
+use std::collections::HashSet; // A hash set gives O(1) membership checks for the two-sum scan.
+use std::fmt; // `fmt::Display` lets `CalcError` render itself as a human-readable message.
+use std::io::{self, IsTerminal, Write}; // `IsTerminal` detects a TTY; `Write` brings `flush` into scope for the prompt.
+
+use clap::{Parser, Subcommand, ValueEnum}; // Derive-based CLI parsing: `#[derive(Parser)]` gives us `--help` and usage errors for free.
+use colorful::Colorful; // Extension trait adding `.green()`, `.bold()`, `.red()` to strings for the `--color` output.
+use rand::Rng; // Brings `gen_range` into scope, as the guessing game already uses.
+
+/// The ways parsing and file loading can fail, carried in a structured form so callers can match on the kind.
+#[derive(Debug, PartialEq)]
+enum CalcError {
+    EmptyInput,              // No tokens were provided at all.
+    UnknownCommand(String),  // The leading verb did not name a known command.
+    BadNumber { piece: String }, // A token could not be parsed as an integer.
+    BadLine { line: usize, piece: String }, // A file line could not be parsed as an integer.
+    MissingOperand,          // A command was given without its required argument(s).
+    Io(String),              // A filesystem read or write failed.
+}
+
+impl fmt::Display for CalcError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CalcError::EmptyInput => write!(f, "no input provided"),
+            CalcError::UnknownCommand(verb) => write!(f, "unknown command: {verb}"),
+            CalcError::BadNumber { piece } => write!(f, "bad number: {piece}"),
+            CalcError::BadLine { line, piece } => write!(f, "line {line}: bad number: '{piece}'"),
+            CalcError::MissingOperand => write!(f, "missing operand"),
+            CalcError::Io(message) => write!(f, "{message}"),
+        }
+    }
+}
+
+impl std::error::Error for CalcError {}
+
+/// When to emit ANSI color, following the usual `auto`/`always`/`never` convention.
+#[derive(Clone, Copy, ValueEnum)]
+enum ColorChoice {
+    Auto,
+    Always,
+    Never,
+}
+
+/// Resolved per-stream coloring decision, computed once from the `--color` flag and TTY detection.
+#[derive(Clone, Copy)]
+struct Colors {
+    out: bool, // Color stdout (totals, list headers)?
+    err: bool, // Color stderr (error messages)?
+}
+
+impl Colors {
+    /// Resolve the flag against each stream: `auto` colors only a stream attached to a terminal.
+    fn resolve(choice: ColorChoice) -> Colors {
+        match choice {
+            ColorChoice::Always => Colors { out: true, err: true },
+            ColorChoice::Never => Colors { out: false, err: false },
+            ColorChoice::Auto => Colors {
+                out: io::stdout().is_terminal(),
+                err: io::stderr().is_terminal(),
+            },
+        }
+    }
+
+    /// Print `text` to stdout in green (used for totals) when stdout coloring is on.
+    fn total(&self, text: String) {
+        if self.out {
+            println!("{}", text.green());
+        } else {
+            println!("{text}");
+        }
+    }
+
+    /// Print `text` to stdout in bold (used for `list` headers) when stdout coloring is on.
+    fn header(&self, text: String) {
+        if self.out {
+            println!("{}", text.bold());
+        } else {
+            println!("{text}");
+        }
+    }
+
+    /// Print an error to stderr in red when stderr coloring is on.
+    fn error(&self, message: impl fmt::Display) {
+        let text = format!("Error: {message}");
+        if self.err {
+            eprintln!("{}", text.red());
+        } else {
+            eprintln!("{text}");
+        }
+    }
+}
+
+/// A small accumulating integer calculator with an interactive REPL.
+#[derive(Parser)]
+#[command(name = "lab", about = "Accumulate integers, total them, and solve n-sum puzzles")]
+struct Cli {
+    /// Subcommand to run once; omit it to drop into the interactive REPL.
+    #[command(subcommand)]
+    command: Option<CliCommands>,
+
+    /// When to colorize output: auto-detects a terminal by default.
+    #[arg(long, value_enum, default_value = "auto")]
+    color: ColorChoice,
+}
+
+#[derive(Subcommand, Debug)]
+enum CliCommands {
+    /// Sum the given numbers and print the total.
+    Add {
+        // `allow_hyphen_values` keeps clap from treating a leading `-` as a flag, so negatives like `-3` parse.
+        #[arg(allow_hyphen_values = true)]
+        numbers: Vec<i32>,
+    },
+    /// Print the numbers and their sum (only meaningful inside the REPL's session).
+    List,
+    /// Leave the program.
+    Quit,
+    /// Read one integer per line from a file and print the total.
+    Input { path: String },
+    /// Find two numbers (read from a file) summing to the target and print their product.
+    Pair {
+        target: i32,
+        /// File of one integer per line to search.
+        #[arg(long)]
+        input: String,
+    },
+    /// Find three numbers (read from a file) summing to the target and print their product.
+    Triple {
+        target: i32,
+        /// File of one integer per line to search.
+        #[arg(long)]
+        input: String,
+    },
+    /// Generate random integers, print their total, and optionally write them to a file.
+    Gen {
+        count: usize,
+        min: i32,
+        max: i32,
+        /// Optional path to write the generated numbers, one per line.
+        #[arg(long)]
+        out: Option<String>,
+    },
+}
 
 #[derive(Debug, PartialEq)]
 enum Command {
     Add(Vec<i32>),
     List,
     Quit,
+    Input(String), // Holds the path to a file of one integer per line.
+    Pair(i32),     // Find two ledger entries summing to the target and print their product.
+    Triple(i32),   // Find three ledger entries summing to the target and print their product.
+    Gen {          // Generate `count` random integers in `min..=max`, optionally writing them to `out`.
+        count: usize,
+        min: i32,
+        max: i32,
+        out: Option<String>,
+    },
 }
 
-fn parse_command(raw: &[String]) -> Result<Command, String> {
+fn parse_command(raw: &[String]) -> Result<Command, CalcError> {
     if raw.is_empty() {
-        return Err("no input provided".into());
+        return Err(CalcError::EmptyInput);
+    }
+
+    // A small helper so every numeric token reports the same structured `BadNumber` error.
+    fn int(piece: &str) -> Result<i32, CalcError> {
+        piece
+            .parse::<i32>()
+            .map_err(|_| CalcError::BadNumber { piece: piece.to_string() })
     }
 
     let verb = raw[0].to_lowercase();
     match verb.as_str() {
         "add" => {
             if raw.len() == 1 {
-                return Err("add needs at least one number".into());
+                return Err(CalcError::MissingOperand);
             }
 
-            let numbers = raw[1..]
-                .iter()
-                .map(|piece| piece.parse::<i32>().map_err(|_| format!("bad number: {piece}")))
-                .collect::<Result<Vec<_>, _>>()?;
+            let numbers = raw[1..].iter().map(|piece| int(piece)).collect::<Result<Vec<_>, _>>()?;
 
             Ok(Command::Add(numbers))
         }
         "list" => Ok(Command::List),
         "quit" => Ok(Command::Quit),
-        other => Err(format!("unknown command: {other}")),
+        "input" => {
+            if raw.len() != 2 {
+                return Err(CalcError::MissingOperand);
+            }
+            Ok(Command::Input(raw[1].clone()))
+        }
+        "pair" | "triple" => {
+            if raw.len() != 2 {
+                return Err(CalcError::MissingOperand);
+            }
+            let target = int(&raw[1])?;
+            if verb == "pair" {
+                Ok(Command::Pair(target))
+            } else {
+                Ok(Command::Triple(target))
+            }
+        }
+        "gen" => {
+            if raw.len() < 4 || raw.len() > 5 {
+                return Err(CalcError::MissingOperand);
+            }
+            let count = raw[1]
+                .parse::<usize>()
+                .map_err(|_| CalcError::BadNumber { piece: raw[1].clone() })?;
+            let min = int(&raw[2])?;
+            let max = int(&raw[3])?;
+            let out = raw.get(4).cloned(); // The optional output path, if a fifth token was given.
+            Ok(Command::Gen { count, min, max, out })
+        }
+        other => Err(CalcError::UnknownCommand(other.to_string())),
     }
 }
 
 /// Borrow the provided argument list and return either parsed integers or a human-readable error.
-fn parse_args(args: &[String]) -> Result<Vec<i32>, String> {
+fn parse_args(args: &[String]) -> Result<Vec<i32>, CalcError> {
     // `&[String]` is a shared slice reference: `&` means "borrow" without taking ownership.
     let mut numbers = Vec::new(); // `Vec::new()` allocates an empty growable array on the heap.
 
@@ -41,30 +224,231 @@ fn parse_args(args: &[String]) -> Result<Vec<i32>, String> {
         // `arg.parse::<i32>()` uses the `FromStr` trait; `::<i32>` (the "turbofish") selects the target type.
         match arg.parse::<i32>() {
             Ok(value) => numbers.push(value), // `=>` separates a match pattern from the code it runs.
-            Err(_) => return Err(format!("Could not parse '{arg}' as an integer")),
-            // `format!` builds a String using `{}` interpolation; `return` exits the function early.
+            Err(_) => return Err(CalcError::BadNumber { piece: arg.clone() }),
+            // `return` exits the function early with the structured error.
         }
     }
 
     Ok(numbers) // `Ok(...)` wraps the success value inside the Result type.
 }
 
+/// Read a file of one integer per line, parsing each non-empty line the same way `parse_args` does.
+fn read_input_file(path: &str) -> Result<Vec<i32>, CalcError> {
+    // `fs::read_to_string` slurps the whole file; IO failures become a structured `Io` error naming the path.
+    let contents = std::fs::read_to_string(path).map_err(|error| CalcError::Io(format!("{path}: {error}")))?;
+
+    let mut numbers = Vec::new();
+    // `enumerate()` pairs each line with its zero-based index; `+ 1` makes the reported line numbers human-friendly.
+    for (index, line) in contents.lines().enumerate() {
+        let trimmed = line.trim();
+        if trimmed.is_empty() {
+            continue; // Skip blank lines so trailing newlines don't count as bad input.
+        }
+        // Reuse `parse_args`' logic per line, promoting a `BadNumber` into a `BadLine` that names the offending line.
+        match parse_args(std::slice::from_ref(&trimmed.to_string())) {
+            Ok(mut parsed) => numbers.append(&mut parsed),
+            Err(CalcError::BadNumber { piece }) => {
+                return Err(CalcError::BadLine { line: index + 1, piece })
+            }
+            Err(other) => return Err(other),
+        }
+    }
+
+    Ok(numbers)
+}
+
+/// Find two entries of `numbers` that sum to `target` and return their product.
+fn pair_product(numbers: &[i32], target: i32) -> Result<i32, String> {
+    // `seen` holds every value we have already visited so a single pass suffices.
+    let mut seen: HashSet<i32> = HashSet::new();
+    for &value in numbers {
+        // `checked_sub` catches the rare case where `target - value` itself over/underflows i32.
+        if let Some(complement) = target.checked_sub(value) {
+            // A value equal to `target / 2` only forms a pair if its complement was *already* inserted on a prior
+            // turn, so the membership check naturally requires two separate occurrences.
+            if seen.contains(&complement) {
+                return value
+                    .checked_mul(complement)
+                    .ok_or_else(|| format!("pair product overflows: {value} * {complement}"));
+            }
+        }
+        seen.insert(value);
+    }
+    Err(format!("no pair sums to {target}"))
+}
+
+/// Find three entries of `numbers` that sum to `target` and return their product.
+fn triple_product(numbers: &[i32], target: i32) -> Result<i32, String> {
+    // Sort a copy so the two-pointer sweep can decide which end to move; the caller's ledger stays untouched.
+    let mut sorted = numbers.to_vec();
+    sorted.sort_unstable();
+
+    for i in 0..sorted.len() {
+        let (mut lo, mut hi) = (i + 1, sorted.len().saturating_sub(1));
+        while lo < hi {
+            // Widen to i64 so three large i32s can't overflow the comparison itself.
+            let sum = sorted[i] as i64 + sorted[lo] as i64 + sorted[hi] as i64;
+            match sum.cmp(&(target as i64)) {
+                std::cmp::Ordering::Equal => {
+                    return sorted[i]
+                        .checked_mul(sorted[lo])
+                        .and_then(|partial| partial.checked_mul(sorted[hi]))
+                        .ok_or_else(|| {
+                            format!(
+                                "triple product overflows: {} * {} * {}",
+                                sorted[i], sorted[lo], sorted[hi]
+                            )
+                        });
+                }
+                std::cmp::Ordering::Less => lo += 1,  // Too small: advance the low pointer to a larger value.
+                std::cmp::Ordering::Greater => hi -= 1, // Too big: retreat the high pointer to a smaller value.
+            }
+        }
+    }
+    Err(format!("no triple sums to {target}"))
+}
+
+/// Produce `count` random integers in the inclusive range `min..=max`.
+fn generate_numbers(count: usize, min: i32, max: i32) -> Result<Vec<i32>, String> {
+    if min > max {
+        return Err(format!("empty range: {min} > {max}"));
+    }
+    let mut rng = rand::thread_rng();
+    // `(0..count).map(...)` draws one sample per iteration; `gen_range` uses the same inclusive form as the guessing game.
+    Ok((0..count).map(|_| rng.gen_range(min..=max)).collect())
+}
+
+/// Write `numbers` to `path` in the one-integer-per-line format the file-input mode reads.
+fn write_numbers_file(path: &str, numbers: &[i32]) -> Result<(), String> {
+    // `map`/`collect`/`join` build the body; the trailing newline keeps the file POSIX-friendly.
+    let body = numbers
+        .iter()
+        .map(i32::to_string)
+        .collect::<Vec<_>>()
+        .join("\n");
+    std::fs::write(path, format!("{body}\n")).map_err(|error| format!("{path}: {error}"))
+}
+
 fn main() {
-    // `env::args()` yields an iterator over arguments; `skip(1)` drops the binary name; `collect()` gathers into Vec<String>.
-    let args: Vec<String> = env::args().skip(1).collect();
+    // `Cli::parse()` reads the process arguments, handling `--help` and usage errors for us.
+    let cli = Cli::parse();
+    let colors = Colors::resolve(cli.color); // Decide coloring once, up front.
+
+    // With no subcommand, drop into the accumulating REPL so `List` has real backing state to report.
+    let Some(command) = cli.command else {
+        repl(&mut Vec::new(), colors);
+        return;
+    };
 
-    // `&args` borrows the vector so `parse_args` can read it without taking ownership.
-    let numbers = match parse_args(&args) {
-        Ok(nums) => nums,
-        Err(message) => {
-            eprintln!("Error: {message}"); // `eprintln!` writes to stderr for error reporting.
-            std::process::exit(1); // Exit with a non-zero status to signal failure to the shell.
+    // Each one-shot subcommand reports its own error to stderr and exits non-zero, matching the old behavior.
+    let result = match command {
+        CliCommands::Add { numbers } => {
+            let total: i32 = numbers.iter().sum();
+            colors.total(format!("Total: {total}"));
+            Ok(())
+        }
+        CliCommands::List => {
+            // One-shot `list` has no ledger to report; direct the user to the REPL instead of printing misleading zeros.
+            Err("`list` has no session state outside the REPL; run `lab` with no subcommand".to_string())
+        }
+        CliCommands::Quit => Ok(()),
+        CliCommands::Input { path } => read_input_file(&path).map_err(|e| e.to_string()).map(|numbers| {
+            let total: i32 = numbers.iter().sum();
+            colors.total(format!("Total: {total}"));
+        }),
+        CliCommands::Pair { target, input } => read_input_file(&input)
+            .map_err(|e| e.to_string())
+            .and_then(|numbers| pair_product(&numbers, target))
+            .map(|product| colors.total(format!("Product: {product}"))),
+        CliCommands::Triple { target, input } => read_input_file(&input)
+            .map_err(|e| e.to_string())
+            .and_then(|numbers| triple_product(&numbers, target))
+            .map(|product| colors.total(format!("Product: {product}"))),
+        CliCommands::Gen { count, min, max, out } => {
+            generate_numbers(count, min, max).and_then(|numbers| {
+                if let Some(path) = &out {
+                    write_numbers_file(path, &numbers)?; // Persist before reporting, propagating any write error.
+                }
+                let total: i32 = numbers.iter().sum();
+                colors.total(format!("Total: {total}"));
+                Ok(())
+            })
         }
     };
 
-    // `iter()` yields references over the numbers; `sum()` consumes the iterator and adds them up.
-    let total: i32 = numbers.iter().sum();
-    println!("Total: {total}"); // `println!` prints with a newline to stdout.
+    if let Err(message) = result {
+        colors.error(message); // Errors go to stderr, in red when appropriate.
+        std::process::exit(1); // Exit with a non-zero status to signal failure to the shell.
+    }
+}
+
+/// Run the interactive loop, mutating `ledger` in place until the user asks to quit.
+fn repl(ledger: &mut Vec<i32>, colors: Colors) {
+    loop {
+        print!("> "); // `print!` omits the trailing newline so the prompt sits on the input line.
+        io::stdout().flush().ok(); // stdout is line-buffered; `flush` forces the prompt out before we block on input.
+
+        let mut line = String::new(); // A fresh buffer for each line we read.
+        match io::stdin().read_line(&mut line) {
+            Ok(0) => break, // `Ok(0)` means end-of-file (e.g. Ctrl-D), so we leave the loop.
+            Ok(_) => {}
+            Err(error) => {
+                eprintln!("Error: {error}");
+                break;
+            }
+        }
+
+        // `split_whitespace` tokenizes the line; `map`/`collect` turn the &str pieces into owned Strings for `parse_command`.
+        let tokens: Vec<String> = line.split_whitespace().map(str::to_string).collect();
+        if tokens.is_empty() {
+            continue; // A blank line is a no-op; prompt again.
+        }
+
+        match parse_command(&tokens) {
+            Ok(Command::Add(numbers)) => {
+                ledger.extend(numbers); // Accumulate the new numbers into the running ledger.
+                let total: i32 = ledger.iter().sum();
+                colors.total(format!("Total: {total}")); // Echo the running total after every `add`.
+            }
+            Ok(Command::List) => {
+                let total: i32 = ledger.iter().sum();
+                colors.header(format!("Numbers: {ledger:?}")); // `{:?}` uses the `Debug` formatting to print the whole vector.
+                colors.header(format!("Sum: {total}"));
+            }
+            Ok(Command::Input(path)) => match read_input_file(&path) {
+                Ok(numbers) => {
+                    ledger.extend(numbers); // Load every number from the file into the ledger.
+                    let total: i32 = ledger.iter().sum();
+                    colors.total(format!("Total: {total}"));
+                }
+                Err(message) => colors.error(message),
+            },
+            Ok(Command::Pair(target)) => match pair_product(ledger, target) {
+                Ok(product) => colors.total(format!("Product: {product}")),
+                Err(message) => colors.error(message),
+            },
+            Ok(Command::Triple(target)) => match triple_product(ledger, target) {
+                Ok(product) => colors.total(format!("Product: {product}")),
+                Err(message) => colors.error(message),
+            },
+            Ok(Command::Gen { count, min, max, out }) => match generate_numbers(count, min, max) {
+                Ok(numbers) => {
+                    // Optionally persist the dataset before loading it, surfacing any write error without discarding the numbers.
+                    if let Some(path) = &out {
+                        if let Err(message) = write_numbers_file(path, &numbers) {
+                            colors.error(message);
+                        }
+                    }
+                    ledger.extend(numbers);
+                    let total: i32 = ledger.iter().sum();
+                    colors.total(format!("Total: {total}"));
+                }
+                Err(message) => colors.error(message),
+            },
+            Ok(Command::Quit) => break,
+            Err(message) => colors.error(message),
+        }
+    }
 }
 
 #[cfg(test)]
@@ -83,6 +467,75 @@ mod tests {
     fn rejects_bad_input() {
         let input = vec!["nope".to_string()];
         let error = parse_args(&input).unwrap_err(); // `unwrap_err` returns the Err value or panics if it was Ok.
-        assert!(error.contains("nope")); // `assert!` checks a boolean expression.
+        // Match on the structured kind instead of substring-matching the rendered message.
+        assert_eq!(error, CalcError::BadNumber { piece: "nope".to_string() });
+    }
+
+    #[test]
+    fn pair_product_handles_duplicate_half() {
+        // Two 3s form the only pair summing to 6; the HashSet scan must wait for the second occurrence.
+        assert_eq!(pair_product(&[3, 1, 3], 6), Ok(9));
+    }
+
+    #[test]
+    fn pair_product_reports_no_solution() {
+        assert_eq!(pair_product(&[1, 2, 4], 100), Err("no pair sums to 100".to_string()));
+    }
+
+    #[test]
+    fn triple_product_finds_triple() {
+        // 1 + 4 + 5 = 10, product 20; the two-pointer sweep over the sorted copy locates it.
+        assert_eq!(triple_product(&[5, 1, 8, 4], 10), Ok(20));
+    }
+
+    #[test]
+    fn triple_product_reports_no_solution() {
+        assert_eq!(triple_product(&[1, 2, 3], 100), Err("no triple sums to 100".to_string()));
+    }
+
+    #[test]
+    fn pair_product_reports_overflow_instead_of_panicking() {
+        // 100_000 * 100_000 overflows i32; this must return an error, not panic.
+        let numbers = [100_000, 100_000];
+        let error = pair_product(&numbers, 200_000).unwrap_err();
+        assert_eq!(error, "pair product overflows: 100000 * 100000");
+    }
+
+    #[test]
+    fn triple_product_reports_overflow_instead_of_panicking() {
+        // 50_000 * 50_000 * 50_000 overflows i32; this must return an error, not panic.
+        let numbers = [50_000, 50_000, 50_000];
+        let error = triple_product(&numbers, 150_000).unwrap_err();
+        assert_eq!(error, "triple product overflows: 50000 * 50000 * 50000");
+    }
+
+    #[test]
+    fn generate_numbers_rejects_inverted_range() {
+        assert_eq!(generate_numbers(3, 5, 1), Err("empty range: 5 > 1".to_string()));
+    }
+
+    #[test]
+    fn read_input_file_reports_bad_line() {
+        // Write a scratch file with a bad value on the second line, then confirm the line-numbered error format.
+        let dir = std::env::temp_dir();
+        let path = dir.join("lab_read_input_file_reports_bad_line.txt");
+        std::fs::write(&path, "1\nfoo\n3\n").expect("write scratch file");
+
+        let path_str = path.to_str().expect("utf-8 path");
+        let error = read_input_file(path_str).unwrap_err();
+        assert_eq!(error, CalcError::BadLine { line: 2, piece: "foo".to_string() });
+        assert_eq!(error.to_string(), "line 2: bad number: 'foo'"); // Exact message format from request #2.
+
+        std::fs::remove_file(&path).ok(); // Tidy up the scratch file.
+    }
+
+    #[test]
+    fn clap_accepts_negative_numbers() {
+        // `try_parse_from` drives the derived parser the way the real binary does, binary name first.
+        let cli = Cli::try_parse_from(["lab", "add", "10", "-3"]).expect("should parse negatives");
+        match cli.command {
+            Some(CliCommands::Add { numbers }) => assert_eq!(numbers, vec![10, -3]),
+            other => panic!("expected Add, got {other:?}"),
+        }
     }
 }